@@ -0,0 +1,170 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use crate::*;
+use crate::mle::{MLEPolynomial, EqPolynomial};
+use crate::virtual_poly::VirtualPolynomial;
+
+/// A sparse `num_rows x num_cols` matrix, stored as `(row, col, value)`
+/// triples.
+pub struct Matrix {
+    pub num_rows: usize,
+    pub num_cols: usize,
+    pub entries: Vec<(usize, usize, Scalar)>,
+}
+
+impl Matrix {
+    pub fn new(num_rows: usize, num_cols: usize, entries: Vec<(usize, usize, Scalar)>) -> Self {
+        for &(r, c, _) in &entries {
+            assert!(r < num_rows);
+            assert!(c < num_cols);
+        }
+        Matrix { num_rows, num_cols, entries }
+    }
+
+    /// Computes `M * z`, as the dense per-row evaluation table of length
+    /// `num_rows`. `MLEPolynomial::new` pads this to the next power of two
+    /// when it is turned into an `MLEPolynomial` over `log(num_rows)`
+    /// variables.
+    pub fn mul_vector(&self, z: &[Scalar]) -> Vec<Scalar> {
+        assert_eq!(z.len(), self.num_cols);
+        let mut result = vec![Scalar::zero(); self.num_rows];
+        for &(r, c, v) in &self.entries {
+            result[r] += v * z[c];
+        }
+        result
+    }
+}
+
+/// A Customizable Constraint System (CCS): matrices `M_0, ..., M_{t-1}`, a
+/// multiset of index-subsets `S_0, ..., S_{q-1}` and coefficients
+/// `c_0, ..., c_{q-1}`, with satisfiability predicate
+///
+/// ```text
+///   sum_k c_k * had_{j in S_k} (M_j * z) = 0
+/// ```
+///
+/// where `had` denotes the Hadamard (entrywise) product. R1CS is the special
+/// case `t = 3`, `q = 2`, `S_0 = {0,1}`, `S_1 = {2}`, see [`R1CS::to_ccs`].
+pub struct CCS {
+    pub num_cons: usize,
+    pub num_vars: usize,
+    pub matrices: Vec<Matrix>,
+    pub multisets: Vec<Vec<usize>>,
+    pub coeffs: Vec<Scalar>,
+}
+
+impl CCS {
+    pub fn new(matrices: Vec<Matrix>, multisets: Vec<Vec<usize>>, coeffs: Vec<Scalar>) -> Self {
+        assert_eq!(multisets.len(), coeffs.len());
+        let num_cons = matrices[0].num_rows;
+        let num_vars = matrices[0].num_cols;
+        for m in &matrices {
+            assert_eq!(m.num_rows, num_cons);
+            assert_eq!(m.num_cols, num_vars);
+        }
+        for s_k in &multisets {
+            for &j in s_k {
+                assert!(j < matrices.len());
+            }
+        }
+        CCS { num_cons, num_vars, matrices, multisets, coeffs }
+    }
+
+    /// Checks `sum_k c_k * had_{j in S_k} (M_j * z) = 0` directly, row by
+    /// row. Useful for sanity-checking a witness before proving it.
+    pub fn is_satisfied(&self, z: &[Scalar]) -> bool {
+        let mz: Vec<Vec<Scalar>> = self.matrices.iter().map(|m| m.mul_vector(z)).collect();
+        (0..self.num_cons).all(|row| {
+            let acc: Scalar = self.multisets.iter().zip(self.coeffs.iter()).map(|(s_k, &c_k)| {
+                c_k * s_k.iter().map(|&j| mz[j][row]).product::<Scalar>()
+            }).sum();
+            acc == Scalar::zero()
+        })
+    }
+
+    /// Builds the zero-check instance `Q(X) = eq(beta, X) * sum_k c_k *
+    /// prod_{j in S_k} (M_j z)(X)`, a [`VirtualPolynomial`] over
+    /// `log(num_cons)` variables whose sum over the hypercube is zero iff
+    /// `is_satisfied(z)` holds. `beta` is the verifier's random challenge
+    /// point, sampled after `z` (or a commitment to it) is fixed.
+    pub fn zero_check_poly(&self, z: &[Scalar], beta: &[Scalar]) -> VirtualPolynomial {
+        let log_m = log_2(self.num_cons.next_power_of_two());
+        assert_eq!(beta.len(), log_m);
+
+        let mut vp = VirtualPolynomial::new(log_m);
+
+        let eq_evals = EqPolynomial::new(&beta.to_vec()).evals_over_hypercube();
+        let eq_handle = vp.add_mle(Rc::new(RefCell::new(MLEPolynomial { num_var: log_m, evals: eq_evals })));
+
+        let mz_handles: Vec<_> = self.matrices.iter().map(|m| {
+            let mz = MLEPolynomial::new(&m.mul_vector(z));
+            vp.add_mle(Rc::new(RefCell::new(mz)))
+        }).collect();
+
+        for (s_k, &c_k) in self.multisets.iter().zip(self.coeffs.iter()) {
+            let mut handles = vec![eq_handle];
+            handles.extend(s_k.iter().map(|&j| mz_handles[j]));
+            vp.add_mle_product(c_k, &handles);
+        }
+
+        vp
+    }
+}
+
+/// A Rank-1 Constraint System `A*z ∘ B*z - C*z = 0`.
+pub struct R1CS {
+    pub a: Matrix,
+    pub b: Matrix,
+    pub c: Matrix,
+}
+
+impl R1CS {
+    /// Lowers to the equivalent CCS: `t = 3` matrices `(A, B, C)`, `q = 2`
+    /// terms `c_0 * (M_0 z ∘ M_1 z) + c_1 * (M_2 z)` with `c_0 = 1`,
+    /// `c_1 = -1`.
+    pub fn to_ccs(self) -> CCS {
+        CCS::new(
+            vec![self.a, self.b, self.c],
+            vec![vec![0, 1], vec![2]],
+            vec![Scalar::one(), Scalar::zero() - Scalar::one()],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two R1CS constraints over z = [a, b, c, d]:
+    //   a * b = c
+    //   d * d = d
+    fn constraints() -> R1CS {
+        let a = Matrix::new(2, 4, vec![(0, 0, Scalar::one()), (1, 3, Scalar::one())]);
+        let b = Matrix::new(2, 4, vec![(0, 1, Scalar::one()), (1, 3, Scalar::one())]);
+        let c = Matrix::new(2, 4, vec![(0, 2, Scalar::one()), (1, 3, Scalar::one())]);
+        R1CS { a, b, c }
+    }
+
+    #[test]
+    fn test_satisfied_instance_zero_checks_to_zero() {
+        let ccs = constraints().to_ccs();
+        let z = Scalar::from_usize_vector(&[2, 3, 6, 1]);
+        assert!(ccs.is_satisfied(&z));
+
+        let beta = Scalar::from_usize_vector(&[7]);
+        let vp = ccs.zero_check_poly(&z, &beta);
+        assert_eq!(vp.sum_over_hypercube(), Scalar::zero());
+    }
+
+    #[test]
+    fn test_unsatisfied_instance_zero_check_is_nonzero() {
+        let ccs = constraints().to_ccs();
+        let z = Scalar::from_usize_vector(&[2, 3, 6, 2]);
+        assert!(!ccs.is_satisfied(&z));
+
+        let beta = Scalar::from_usize_vector(&[7]);
+        let vp = ccs.zero_check_poly(&z, &beta);
+        assert_ne!(vp.sum_over_hypercube(), Scalar::zero());
+    }
+}
@@ -0,0 +1,118 @@
+use crate::*;
+use crate::mle::EqPolynomial;
+use crate::util::lagrange_basis_eval_all;
+
+/// One of the `k` instances to be folded together: its constraint's
+/// error/slack evaluation `f_i`, and its witness vector `w_i`.
+pub struct Instance {
+    pub error: Scalar,
+    pub witness: Vec<Scalar>,
+}
+
+/// The result of folding `k` [`Instance`]s into one.
+pub struct FoldedInstance {
+    pub error: Scalar,
+    pub witness: Vec<Scalar>,
+}
+
+/// The Lagrange basis `L_0(x), ..., L_{domain_size-1}(x)` over the domain
+/// `{0, 1, ..., domain_size-1}`, evaluated at `x`.
+pub fn lagrange_eval_all(domain_size: usize, x: &Scalar) -> Vec<Scalar> {
+    lagrange_basis_eval_all(domain_size, x)
+}
+
+/// Folds `k = instances.len()` instances that share the same constraint
+/// polynomial into one, ProtoGalaxy-style:
+///
+/// - the verifier challenge `beta` (of length `log2(k)`) is turned into the
+///   `k` power-of-challenge weights `pow_i(beta)` by reusing
+///   `EqPolynomial::evals_over_hypercube`, in `O(k)`;
+/// - the folded error is the weighted sum `F(beta) = sum_i pow_i(beta) * f_i`;
+/// - the witnesses are combined at an independent random point `delta` via
+///   the Lagrange basis over the `k` instance indices,
+///   `w = sum_i L_i(delta) * w_i`.
+pub fn fold_instances(instances: &[Instance], beta: &[Scalar], delta: &Scalar) -> FoldedInstance {
+    assert!(!instances.is_empty());
+    let k = instances.len();
+
+    let pow = EqPolynomial::new(&beta.to_vec()).evals_over_hypercube();
+    assert!(pow.len() >= k);
+
+    let folded_error: Scalar = instances.iter().zip(pow.iter())
+        .map(|(inst, &p)| p * inst.error)
+        .sum();
+
+    let l = lagrange_eval_all(k, delta);
+    let witness_len = instances[0].witness.len();
+    for inst in instances {
+        assert_eq!(inst.witness.len(), witness_len);
+    }
+    let folded_witness = (0..witness_len).map(|idx| {
+        instances.iter().zip(l.iter())
+            .map(|(inst, &li)| li * inst.witness[idx])
+            .sum()
+    }).collect();
+
+    FoldedInstance { error: folded_error, witness: folded_witness }
+}
+
+/// Verifies that `folded_error` is the correct combination of the
+/// per-instance `errors` under the verifier's own challenge `beta`, without
+/// touching the (potentially large) witnesses.
+pub fn verify_folded_error(errors: &[Scalar], beta: &[Scalar], folded_error: Scalar) -> bool {
+    let pow = EqPolynomial::new(&beta.to_vec()).evals_over_hypercube();
+    if pow.len() < errors.len() {
+        return false;
+    }
+    let expected: Scalar = errors.iter().zip(pow.iter())
+        .map(|(&e, &p)| p * e)
+        .sum();
+    expected == folded_error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lagrange_eval_all_is_zero_one_on_nodes() {
+        let domain_size = 4;
+        for i in 0..domain_size {
+            let l = lagrange_eval_all(domain_size, &Scalar::from(i as u64));
+            for (j, &lj) in l.iter().enumerate() {
+                if j == i {
+                    assert_eq!(lj, Scalar::one());
+                } else {
+                    assert_eq!(lj, Scalar::zero());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fold_instances_matches_verify_folded_error() {
+        let instances = vec![
+            Instance { error: Scalar::from(3), witness: Scalar::from_usize_vector(&[1,2]) },
+            Instance { error: Scalar::from(5), witness: Scalar::from_usize_vector(&[3,4]) },
+            Instance { error: Scalar::from(7), witness: Scalar::from_usize_vector(&[5,6]) },
+            Instance { error: Scalar::from(11), witness: Scalar::from_usize_vector(&[7,8]) },
+        ];
+        let errors: Vec<Scalar> = instances.iter().map(|inst| inst.error).collect();
+
+        let beta = Scalar::from_usize_vector(&[9, 10]);
+        let delta = Scalar::from(13);
+
+        let folded = fold_instances(&instances, &beta, &delta);
+        assert!(verify_folded_error(&errors, &beta, folded.error));
+
+        // tampering with the folded error must be rejected
+        assert!(!verify_folded_error(&errors, &beta, folded.error + Scalar::one()));
+
+        // the folded witness is the Lagrange combination of the instance witnesses
+        let l = lagrange_eval_all(instances.len(), &delta);
+        let expected_witness: Vec<Scalar> = (0..2).map(|idx| {
+            instances.iter().zip(l.iter()).map(|(inst, &li)| li * inst.witness[idx]).sum()
+        }).collect();
+        assert_eq!(folded.witness, expected_witness);
+    }
+}
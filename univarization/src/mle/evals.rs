@@ -0,0 +1,160 @@
+use crate::*;
+#[cfg(feature = "parallel")]
+use crate::parallel::PARALLEL_THRESHOLD;
+
+/// One round of the butterfly, `evals[i+step+k] -= evals[i+k]` over every
+/// block of `2*step` entries. Blocks are independent of one another, so this
+/// is parallelized over blocks once the table is large enough.
+fn coeffs_round_sequential(evals: &mut [Scalar], step: usize) {
+    for chunk in evals.chunks_mut(2 * step) {
+        let (lo, hi) = chunk.split_at_mut(step);
+        for (l, h) in lo.iter().zip(hi.iter_mut()) {
+            *h -= *l;
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn coeffs_round_parallel(evals: &mut [Scalar], step: usize) {
+    use rayon::prelude::*;
+    evals.par_chunks_mut(2 * step).for_each(|chunk| {
+        let (lo, hi) = chunk.split_at_mut(step);
+        for (l, h) in lo.iter().zip(hi.iter_mut()) {
+            *h -= *l;
+        }
+    });
+}
+
+fn coeffs_round(evals: &mut [Scalar], step: usize) {
+    #[cfg(feature = "parallel")]
+    if evals.len() >= PARALLEL_THRESHOLD {
+        return coeffs_round_parallel(evals, step);
+    }
+    coeffs_round_sequential(evals, step)
+}
+
+/// One round of the inverse butterfly, `coeffs[i+step+k] += coeffs[i+k]`.
+fn evals_round_sequential(coeffs: &mut [Scalar], step: usize) {
+    for chunk in coeffs.chunks_mut(2 * step) {
+        let (lo, hi) = chunk.split_at_mut(step);
+        for (l, h) in lo.iter().zip(hi.iter_mut()) {
+            *h += *l;
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn evals_round_parallel(coeffs: &mut [Scalar], step: usize) {
+    use rayon::prelude::*;
+    coeffs.par_chunks_mut(2 * step).for_each(|chunk| {
+        let (lo, hi) = chunk.split_at_mut(step);
+        for (l, h) in lo.iter().zip(hi.iter_mut()) {
+            *h += *l;
+        }
+    });
+}
+
+fn evals_round(coeffs: &mut [Scalar], step: usize) {
+    #[cfg(feature = "parallel")]
+    if coeffs.len() >= PARALLEL_THRESHOLD {
+        return evals_round_parallel(coeffs, step);
+    }
+    evals_round_sequential(coeffs, step)
+}
+
+/// Interpolates hypercube evaluations into coefficients, in place, in
+/// `O(N log N)` field additions and no allocation.
+///
+/// This is the standard in-place butterfly: for `round = 0, 1, ..., num_var-1`
+/// with `step = 2^round`, every block of `2*step` entries is split into a
+/// lower half `[i, i+step)` and an upper half `[i+step, i+2*step)`, and each
+/// upper-half entry is replaced by its difference with the corresponding
+/// lower-half entry. After `num_var` rounds every entry holds its
+/// coefficient.
+pub fn compute_coeffs_from_evals_in_place(evals: &mut [Scalar]) {
+    let len = evals.len();
+    assert!(len.is_power_of_two());
+    let num_var = log_2(len);
+
+    let mut step = 1;
+    for _round in 0..num_var {
+        coeffs_round(evals, step);
+        step *= 2;
+    }
+}
+
+/// The inverse of [`compute_coeffs_from_evals_in_place`]: turns coefficients
+/// back into hypercube evaluations, in place. Same butterfly, but each
+/// upper-half entry is accumulated instead of subtracted.
+pub fn compute_evals_from_coeffs_in_place(coeffs: &mut [Scalar]) {
+    let len = coeffs.len();
+    assert!(len.is_power_of_two());
+    let num_var = log_2(len);
+
+    let mut step = 1;
+    for _round in 0..num_var {
+        evals_round(coeffs, step);
+        step *= 2;
+    }
+}
+
+/// Interpolate the evaluations into coefficients over hypercube.
+/// Allocating wrapper around [`compute_coeffs_from_evals_in_place`].
+pub fn compute_coeffs_from_evals(evals: &Vec<Scalar>) -> Vec<Scalar> {
+    let mut coeffs = evals.clone();
+    compute_coeffs_from_evals_in_place(&mut coeffs);
+    coeffs
+}
+
+/// Compute all evaluations over hypercube from coefficients.
+/// Allocating wrapper around [`compute_evals_from_coeffs_in_place`].
+pub fn compute_evals_from_coeffs(num_var: usize, coeffs: &[Scalar]) -> Vec<Scalar> {
+    let len = pow_2(num_var);
+    assert!(coeffs.len() <= len);
+
+    let mut evals = coeffs.to_vec();
+    let zeros = vec![Scalar::zero(); len - coeffs.len()];
+    evals.extend(zeros.into_iter());
+
+    compute_evals_from_coeffs_in_place(&mut evals);
+    evals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coeffs_evals_roundtrip() {
+        let coeffs = Scalar::from_usize_vector(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let evals = compute_evals_from_coeffs(3, &coeffs);
+        let coeffs_prime = compute_coeffs_from_evals(&evals);
+        assert_eq!(coeffs, coeffs_prime);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_coeffs_round_parallel_matches_sequential() {
+        let vs = Scalar::from_usize_vector(&(0..16usize).collect::<Vec<usize>>());
+        for &step in &[1, 2, 4] {
+            let mut seq = vs.clone();
+            coeffs_round_sequential(&mut seq, step);
+            let mut par = vs.clone();
+            coeffs_round_parallel(&mut par, step);
+            assert_eq!(seq, par);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_evals_round_parallel_matches_sequential() {
+        let vs = Scalar::from_usize_vector(&(0..16usize).collect::<Vec<usize>>());
+        for &step in &[1, 2, 4] {
+            let mut seq = vs.clone();
+            evals_round_sequential(&mut seq, step);
+            let mut par = vs.clone();
+            evals_round_parallel(&mut par, step);
+            assert_eq!(seq, par);
+        }
+    }
+}
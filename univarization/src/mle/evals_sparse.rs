@@ -0,0 +1,113 @@
+use crate::*;
+use super::{EqPolynomial, MLEPolynomial};
+
+/// A multilinear extension represented by only its nonzero evaluations over
+/// the boolean hypercube `{0,1}^num_var`.
+///
+/// This is the sparse counterpart of [`MLEPolynomial`], useful for the
+/// low-density vectors that show up as matrix-vector products in constraint
+/// systems, where `evaluate` would otherwise pay for `2^num_var` zeros.
+pub struct SparseMLEPolynomial {
+    pub num_var: usize,
+    pub entries: Vec<(usize, Scalar)>,
+}
+
+impl SparseMLEPolynomial {
+    pub fn new(num_var: usize, entries: Vec<(usize, Scalar)>) -> Self {
+        for &(i, _) in &entries {
+            assert!(i < pow_2(num_var));
+        }
+        SparseMLEPolynomial { num_var, entries }
+    }
+
+    /// Drops the zero entries of a dense `MLEPolynomial`.
+    pub fn from_dense(mle: &MLEPolynomial) -> Self {
+        let entries = mle.evals.iter().enumerate()
+            .filter(|(_, v)| **v != Scalar::zero())
+            .map(|(i, v)| (i, *v))
+            .collect();
+        SparseMLEPolynomial { num_var: mle.num_var, entries }
+    }
+
+    /// Materializes the full dense table, re-introducing the zeros.
+    pub fn to_dense(&self) -> MLEPolynomial {
+        let mut evals = vec![Scalar::zero(); pow_2(self.num_var)];
+        for &(i, v) in &self.entries {
+            evals[i] = v;
+        }
+        MLEPolynomial { num_var: self.num_var, evals }
+    }
+
+    /// Evaluates at `rs`, in `O(nnz * log N)` instead of the `O(N)` a dense
+    /// `MLEPolynomial::evaluate` would cost.
+    pub fn evaluate(&self, rs: &[Scalar]) -> Scalar {
+        assert_eq!(rs.len(), self.num_var);
+        let eq = EqPolynomial::new(&rs.to_vec());
+        self.entries.iter().map(|&(i, v)| v * eq.eval(i)).sum()
+    }
+
+    /// Folding the space from N-dim to (N-1)-dim, preserving sparsity: pairs
+    /// `i` and `i+half` are merged exactly as in
+    /// [`MLEPolynomial::fold_into_half`], but entries that are zero on both
+    /// sides of a pair are simply dropped.
+    pub fn fold_into_half(&mut self, rho: &Scalar) {
+        let half = pow_2(self.num_var - 1);
+
+        let mut merged: std::collections::HashMap<usize, Scalar> = std::collections::HashMap::new();
+        for &(i, v) in &self.entries {
+            let (lo, contribution) = if i < half {
+                (i, (Scalar::one() - rho) * v)
+            } else {
+                (i - half, *rho * v)
+            };
+            *merged.entry(lo).or_insert(Scalar::zero()) += contribution;
+        }
+
+        self.entries = merged.into_iter()
+            .filter(|(_, v)| *v != Scalar::zero())
+            .collect();
+        self.num_var -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> (MLEPolynomial, SparseMLEPolynomial) {
+        let dense = MLEPolynomial::new(&Scalar::from_usize_vector(&[0,3,0,0,5,0,0,7]));
+        let sparse = SparseMLEPolynomial::from_dense(&dense);
+        (dense, sparse)
+    }
+
+    #[test]
+    fn test_from_dense_drops_zeros() {
+        let (_, sparse) = sample();
+        assert_eq!(sparse.entries.len(), 3);
+    }
+
+    #[test]
+    fn test_to_dense_roundtrip() {
+        let (dense, sparse) = sample();
+        assert_eq!(sparse.to_dense().evals, dense.evals);
+    }
+
+    #[test]
+    fn test_evaluate_matches_dense() {
+        let (dense, sparse) = sample();
+        let rs = Scalar::from_usize_vector(&[2, 3, 4]);
+        assert_eq!(sparse.evaluate(&rs), dense.evaluate(&rs));
+    }
+
+    #[test]
+    fn test_fold_into_half_matches_dense() {
+        let (mut dense, mut sparse) = sample();
+        let rho = Scalar::from(5);
+
+        dense.fold_into_half(&rho);
+        sparse.fold_into_half(&rho);
+
+        assert_eq!(sparse.num_var, dense.num_var);
+        assert_eq!(sparse.to_dense().evals, dense.evals);
+    }
+}
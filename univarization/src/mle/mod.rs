@@ -8,11 +8,40 @@ use std::collections::HashMap;
 
 use crate::*;
 use crate::bits::*;
+#[cfg(feature = "parallel")]
+use crate::parallel::PARALLEL_THRESHOLD;
 
 pub mod evals;
 pub mod coeffs_sparse;
 pub mod evals_sparse;
 
+/// One layer of [`EqPolynomial::evals_over_hypercube`]'s doubling: `hi[k]`
+/// becomes `lo[k] * x`, and `lo[k]` is reduced to `lo[k] * (1-x)` via
+/// `lo[k] - hi[k]`.
+fn eq_layer_sequential(lo: &mut [Scalar], hi: &mut [Scalar], x: Scalar) {
+    for (l, h) in lo.iter_mut().zip(hi.iter_mut()) {
+        *h = *l * x;
+        *l -= *h;
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn eq_layer_parallel(lo: &mut [Scalar], hi: &mut [Scalar], x: Scalar) {
+    use rayon::prelude::*;
+    lo.par_iter_mut().zip(hi.par_iter_mut()).for_each(|(l, h)| {
+        *h = *l * x;
+        *l -= *h;
+    });
+}
+
+fn eq_layer(lo: &mut [Scalar], hi: &mut [Scalar], x: Scalar) {
+    #[cfg(feature = "parallel")]
+    if lo.len() >= PARALLEL_THRESHOLD {
+        return eq_layer_parallel(lo, hi, x);
+    }
+    eq_layer_sequential(lo, hi, x)
+}
+
 pub struct EqPolynomial {
     x_vec: Vec<Scalar>,
 }
@@ -160,22 +189,12 @@ impl EqPolynomial {
 
         let log_size = self.x_vec.len();
         let full_size = pow_2(log_size);
-        
+
         let mut evals = vec![Scalar::one(); full_size];
         let mut half = 1;
         for i in 0..log_size {
-            for j in 0..half {
-                evals[j+half] = evals[j] * x_vec[i];
-
-                // Normally, we should have computed `evals[j]` via 
-                //    evals[j] = evals[j] * (Scalar::one() - x_vec[i])
-                // However we can save one multiplication by computing
-                //    evals[j] = evals[j] * (Scalar::one() - x_vec[i])
-                //             = evals[j] - evals[j] * x_vec[i]
-                //             = evals[j] - evals[j+half]
-                // evals[j] = evals[j] * (Scalar::one() - x_vec[i]);
-                evals[j] = evals[j] - evals[j+half];
-            }
+            let (lo, hi) = evals[..2*half].split_at_mut(half);
+            eq_layer(lo, hi, x_vec[i]);
             half *= 2;
         }
         evals
@@ -227,19 +246,114 @@ impl EqPolynomial {
         evals
     }
 
-    // TODO: 
+    // TODO:
     pub fn to_evals() -> Vec<Scalar> {
         unimplemented!();
     }
 }
 
-/// Interpolate the evaluations into coefficients over hypercube.
-/// The asymptotic complexity is O(N * log^2(N)).
-/// 
-/// TODO: can we compute in place (without memory allocation)?
+/// A multilinear extension (MLE), represented by its evaluations over the
+/// boolean hypercube `{0,1}^num_var`.
+pub struct MLEPolynomial {
+    pub num_var: usize,
+    pub evals: Vec<Scalar>, // Hello, hypercube!
+}
+
+impl MLEPolynomial {
+    pub fn new(vs: &[Scalar]) -> Self {
+        let vs_len = vs.len();
+        let mut evals = vs.to_vec();
+        let full_len = vs_len.next_power_of_two();
+
+        let num_var = log_2(full_len);
+
+        let padded_len = full_len - vs_len;
+        let padded_vec = vec![Scalar::zero(); padded_len];
+
+        evals.extend(padded_vec);
+
+        MLEPolynomial {
+            num_var: num_var,
+            evals: evals,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.evals.len()
+    }
+
+    // Folding the space from N-dim to (N-1)-dim
+    pub fn fold_into_half(&mut self, rho: &Scalar) {
+        let half = self.len() / 2;
+        let (lo, hi) = self.evals.split_at_mut(half);
+        fold_layer(lo, hi, rho);
+        self.num_var -= 1;
+    }
+
+    pub fn evaluate(&self, rs: &[Scalar]) -> Scalar {
+        assert_eq!(rs.len(), self.num_var);
+
+        // chi is lagrange polynomials evaluated at rs
+        let chi_vec = EqPolynomial::new(&rs.to_vec()).evals_over_hypercube();
+
+        assert_eq!(chi_vec.len(), self.evals.len());
+        dot_product(&chi_vec, &self.evals)
+    }
+
+}
+
+fn fold_layer_sequential(lo: &mut [Scalar], hi: &[Scalar], rho: &Scalar) {
+    for (l, h) in lo.iter_mut().zip(hi.iter()) {
+        *l = (Scalar::one() - rho) * *l + *rho * *h;
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn fold_layer_parallel(lo: &mut [Scalar], hi: &[Scalar], rho: &Scalar) {
+    use rayon::prelude::*;
+    lo.par_iter_mut().zip(hi.par_iter()).for_each(|(l, h)| {
+        *l = (Scalar::one() - rho) * *l + *rho * *h;
+    });
+}
+
+fn fold_layer(lo: &mut [Scalar], hi: &[Scalar], rho: &Scalar) {
+    #[cfg(feature = "parallel")]
+    if lo.len() >= PARALLEL_THRESHOLD {
+        return fold_layer_parallel(lo, hi, rho);
+    }
+    fold_layer_sequential(lo, hi, rho)
+}
+
+fn dot_product_sequential(a: &[Scalar], b: &[Scalar]) -> Scalar {
+    a.iter().zip(b.iter()).map(|(&x, &y)| x * y).sum()
+}
+
+#[cfg(feature = "parallel")]
+fn dot_product_parallel(a: &[Scalar], b: &[Scalar]) -> Scalar {
+    use rayon::prelude::*;
+    a.par_iter().zip(b.par_iter()).map(|(&x, &y)| x * y).sum()
+}
+
+fn dot_product(a: &[Scalar], b: &[Scalar]) -> Scalar {
+    #[cfg(feature = "parallel")]
+    if a.len() >= PARALLEL_THRESHOLD {
+        return dot_product_parallel(a, b);
+    }
+    dot_product_sequential(a, b)
+}
+
+impl Index<usize> for MLEPolynomial {
+    type Output = Scalar;
+
+    // TODO: inline
+    fn index(&self, index: usize) -> &Self::Output {
+        &(self.evals[index])
+    }
+}
+
+/// Interpolation between hypercube evaluations and coefficients, in both
+/// directions:
 ///
-/// The argument evals: the evaluations of the MLE over hypercube
-/// 
 /// ```
 ///    evals = [0b000: e0 paired with (1-X0)(1-X1)(1-X2),
 ///             0b001: e1 paired with  X0   (1-X1)(1-X2),
@@ -247,14 +361,12 @@ impl EqPolynomial {
 ///             0b011: e3 paired with  X0     X1  (1-X2),
 ///             0b100: e4 paired with (1-X0)(1-X1)  X2  ,
 ///             0b101: e5 paired with  X0   (1-X1)  X2  ,
-///             0b110: e6 paired with (1-X0)  X1    X2  , 
+///             0b110: e6 paired with (1-X0)  X1    X2  ,
 ///             0b111: e7 paired with  X0     X1    X2  ,
-///            ]    
+///            ]
 /// ```
-/// Return coeffs: the coefficients of the MLE
-/// 
 /// ```
-///   coeffs = [0b000: c0 of constant term, 
+///   coeffs = [0b000: c0 of constant term,
 ///             0b001: c1 of X0           ,
 ///             0b010: c2 of    X1        ,
 ///             0b011: c3 of X0 X1        ,
@@ -264,79 +376,87 @@ impl EqPolynomial {
 ///             0b111: c7 of X0 X1 X2     ,
 ///            ]
 /// ```
+/// See [`evals`] for the `O(N log N)`, in-place implementation.
+pub use evals::{
+    compute_coeffs_from_evals, compute_evals_from_coeffs,
+    compute_coeffs_from_evals_in_place, compute_evals_from_coeffs_in_place,
+};
 
-pub fn compute_coeffs_from_evals(evals: &Vec<Scalar>) -> Vec<Scalar> {
-    let mut coeffs = evals.clone();
-    let len = coeffs.len();
-    assert!(len.is_power_of_two());
-    let num_var = log_2(len);
-
-    let mut half = len / 2;
-    for _i in 0..num_var {
-        let b = len / half;
-        for j in (0..b).step_by(2) {
-            for k in 0..half {
-                let a = coeffs[j*half + k];
-                coeffs[(j+1)*half + k] -= a;
-            }
-        }
-        half = half / 2;
-    };
-    coeffs
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-/// Compute all evaluations over hypercube from coefficients.
-/// The asymptotic complexity is O(N*log^2(N)).
-/// 
-/// Arugment coeffs: the coefficients of the polynomial (non-sparse form)
-/// 
-/// ```
-///   coeffs = [0b000: c0 of constant term, 
-///             0b001: c1 of X0           ,
-///             0b010: c2 of    X1        ,
-///             0b011: c3 of X0 X1        ,
-///             0b100: c4 of       X2     ,
-///             0b101: c5 of X0    X2     ,
-///             0b110: c6 of    X1 X2     ,
-///             0b111: c7 of X0 X1 X2     ,
-///            ]
-/// ```
-/// 
-/// Return evals: the evaluations of the polynomial (non-sparse form)
-/// 
-/// ```
-///    evals = [0b000: e0 paired with (1-X0)(1-X1)(1-X2),
-///             0b001: e1 paired with  X0   (1-X1)(1-X2),
-///             0b010: e2 paired with (1-X0)  X1  (1-X2),
-///             0b011: e3 paired with  X0     X1  (1-X2),
-///             0b100: e4 paired with (1-X0)(1-X1)  X2  ,
-///             0b101: e5 paired with  X0   (1-X1)  X2  ,
-///             0b110: e6 paired with (1-X0)  X1    X2  , 
-///             0b111: e7 paired with  X0     X1    X2  ,
-///            ]    
-/// ```
-///
-pub fn compute_evals_from_coeffs(num_var: usize, coeffs: &[Scalar]) -> Vec<Scalar> {
-    let len = pow_2(num_var);
-    assert!(coeffs.len() <= len);
-    let mut evals = coeffs.to_vec();
-
-    // Padding zeros to match the length of the hypercube
-    let zeros = vec![Scalar::zero(); len - coeffs.len()];
-    evals.extend(zeros.into_iter());
-
-    // Initialize the position of folding
-    let mut half = len / 2; // number of blocks
-
-    for _i in 0..num_var {
-        for j in 0..half {
-            let s = len/half; // size of each block
-            for k in 0..s/2 {  // tranverse over the top-half of the block 
-                let a = evals[j*s + k];
-                evals[j*s + k + (s/2)] += a;
-            }
-        }
-        half = half / 2;
+    #[test]
+    fn test_eq_new() {
+        let vs = Scalar::from_usize_vector(&[1,2,3]);
+        let _eq = EqPolynomial::new(&vs);
+    }
+
+    #[test]
+    fn test_eq_evals_over_hypercube() {
+        let vs = Scalar::from_usize_vector(&[1,2,3]);
+        let eq = EqPolynomial::new(&vs);
+        let evals = eq.evals_over_hypercube();
+        let evals_prime = eq.evals_over_hypercube_slow();
+        assert_eq!(evals, evals_prime);
+    }
+
+    #[test]
+    fn test_mle_new() {
+        let vs = Scalar::from_usize_vector(&[1,2,3,4]);
+        let mle = MLEPolynomial::new(&vs);
+        assert_eq!(mle.len(), 4);
+        assert_eq!(mle.num_var, 2);
+        assert_eq!(mle.evals, Scalar::from_usize_vector(&[1,2,3,4]));
+    }
+
+    #[test]
+    fn test_mle_new_again() {
+        let vs = Scalar::from_usize_vector(&[1,2,3,4,5]);
+        let mle = MLEPolynomial::new(&vs);
+        assert_eq!(mle.len(), 8);
+        assert_eq!(mle.num_var, 3);
+        assert_eq!(mle.evals, Scalar::from_usize_vector(&[1,2,3,4,5,0,0,0]));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_eq_layer_parallel_matches_sequential() {
+        let x = Scalar::from(7);
+        let mut lo_seq = Scalar::from_usize_vector(&(0..16usize).collect::<Vec<usize>>());
+        let mut hi_seq = vec![Scalar::zero(); 16];
+        let mut lo_par = lo_seq.clone();
+        let mut hi_par = hi_seq.clone();
+
+        eq_layer_sequential(&mut lo_seq, &mut hi_seq, x);
+        eq_layer_parallel(&mut lo_par, &mut hi_par, x);
+
+        assert_eq!(lo_seq, lo_par);
+        assert_eq!(hi_seq, hi_par);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_fold_layer_parallel_matches_sequential() {
+        let rho = Scalar::from(3);
+        let lo = Scalar::from_usize_vector(&(0..16usize).collect::<Vec<usize>>());
+        let hi = Scalar::from_usize_vector(&(16..32usize).collect::<Vec<usize>>());
+
+        let mut lo_seq = lo.clone();
+        fold_layer_sequential(&mut lo_seq, &hi, &rho);
+
+        let mut lo_par = lo.clone();
+        fold_layer_parallel(&mut lo_par, &hi, &rho);
+
+        assert_eq!(lo_seq, lo_par);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_dot_product_parallel_matches_sequential() {
+        let a = Scalar::from_usize_vector(&(0..16usize).collect::<Vec<usize>>());
+        let b = Scalar::from_usize_vector(&(16..32usize).collect::<Vec<usize>>());
+
+        assert_eq!(dot_product_sequential(&a, &b), dot_product_parallel(&a, &b));
     }
-    evals
 }
\ No newline at end of file
@@ -0,0 +1,4 @@
+/// Below this many entries, a hot loop's sequential path outruns the thread
+/// dispatch overhead of going parallel, so the `parallel` feature's hot
+/// loops only switch over once a layer/table reaches this size.
+pub const PARALLEL_THRESHOLD: usize = 1 << 10;
@@ -0,0 +1,331 @@
+use crate::*;
+use crate::mle::{MLEPolynomial, EqPolynomial};
+use crate::transcript::Transcript;
+use crate::util::lagrange_basis_eval_all;
+use crate::virtual_poly::VirtualPolynomial;
+
+/// The per-round messages of a sum-check proof.
+///
+/// Round `i` holds the evaluations of the univariate `s_i(X) = sum_{x} g(X, x)`
+/// at `X = 0, 1, ..., d`, where `d` is the number of factors of `g` (a bound
+/// on the degree of `g` in each single variable, since every factor is
+/// multilinear).
+pub struct SumCheckProof {
+    pub round_evals: Vec<Vec<Scalar>>,
+}
+
+/// Proves that `H = sum_{x in {0,1}^n} g(x)`, where `g = f_1 * f_2 * ... * f_d`
+/// is the product of `factors`, all sharing the same `num_var = n`.
+///
+/// `factors` is folded in place, round by round, exactly like
+/// [`MLEPolynomial::fold_into_half`]; on return every factor has been folded
+/// down to a single value (`num_var = 0`): `f_j` at the point where `r_1`
+/// binds the last variable and `r_n` the first `X_0`.
+///
+/// Returns the claimed sum `H` and the proof; the challenges `r_1, ..., r_n`
+/// can be read off `transcript` by replaying [`verify`].
+pub fn prove(factors: &mut [MLEPolynomial], transcript: &mut Transcript) -> (Scalar, SumCheckProof) {
+    assert!(!factors.is_empty());
+    let num_var = factors[0].num_var;
+    for f in factors.iter() {
+        assert_eq!(f.num_var, num_var);
+    }
+
+    let d = factors.len();
+    let claimed_sum = product_sum(factors);
+    transcript.append_scalar(b"sumcheck-claimed-sum", &claimed_sum);
+
+    let mut round_evals = Vec::with_capacity(num_var);
+
+    for _round in 0..num_var {
+        let half = factors[0].len() / 2;
+
+        // s_i(t) = sum_{j < half} prod_k ((1-t)*f_k[j] + t*f_k[j+half])
+        let mut s_i = Vec::with_capacity(d + 1);
+        for t in 0..=d {
+            let t = Scalar::from(t as u64);
+            let sum_t: Scalar = (0..half).map(|j| {
+                factors.iter().map(|f| {
+                    (Scalar::one() - t) * f.evals[j] + t * f.evals[j + half]
+                }).product::<Scalar>()
+            }).sum();
+            s_i.push(sum_t);
+        }
+
+        transcript.append_scalars(b"sumcheck-round-poly", &s_i);
+        let r_i = transcript.challenge_scalar(b"sumcheck-challenge");
+
+        for f in factors.iter_mut() {
+            f.fold_into_half(&r_i);
+        }
+
+        round_evals.push(s_i);
+    }
+
+    (claimed_sum, SumCheckProof { round_evals })
+}
+
+/// Verifies a sum-check proof that `claimed_sum = sum_x f_1(x)*...*f_d(x)`.
+///
+/// `factors` are used only for the final check, `prod_j f_j(r_1,...,r_n)`;
+/// in the zero-check instantiation below, one of them is the `eq` polynomial
+/// that the verifier can build on its own, while the others would typically
+/// be opened via a polynomial commitment instead of sent in the clear.
+pub fn verify(factors: &[MLEPolynomial], claimed_sum: Scalar, proof: &SumCheckProof, transcript: &mut Transcript) -> bool {
+    assert!(!factors.is_empty());
+    let num_var = factors[0].num_var;
+    let degree = factors.len();
+
+    if proof.round_evals.len() != num_var {
+        return false;
+    }
+
+    transcript.append_scalar(b"sumcheck-claimed-sum", &claimed_sum);
+
+    let mut claim = claimed_sum;
+    let mut challenges = Vec::with_capacity(num_var);
+
+    for s_i in &proof.round_evals {
+        if s_i.len() != degree + 1 {
+            return false;
+        }
+        if s_i[0] + s_i[1] != claim {
+            return false;
+        }
+
+        transcript.append_scalars(b"sumcheck-round-poly", s_i);
+        let r_i = transcript.challenge_scalar(b"sumcheck-challenge");
+
+        claim = eval_univariate(s_i, &r_i);
+        challenges.push(r_i);
+    }
+
+    // Round i binds the highest variable still free (see `fold_into_half`),
+    // so the challenges come in reverse order of `evaluate`'s variables.
+    let point: Vec<Scalar> = challenges.iter().rev().cloned().collect();
+    let expected: Scalar = factors.iter().map(|f| f.evaluate(&point)).product();
+    claim == expected
+}
+
+/// Like [`prove`], but for a general [`VirtualPolynomial`] (a sum of
+/// products of MLEs) rather than a single bare product. `f` is folded in
+/// place, round by round; on return every underlying MLE has been folded
+/// down to a single value.
+pub fn prove_virtual(f: &mut VirtualPolynomial, transcript: &mut Transcript) -> (Scalar, SumCheckProof) {
+    let num_var = f.num_var;
+    let d = f.max_degree();
+
+    let claimed_sum = virtual_sum(f);
+    transcript.append_scalar(b"sumcheck-claimed-sum", &claimed_sum);
+
+    let mut round_evals = Vec::with_capacity(num_var);
+
+    for _round in 0..num_var {
+        let half = f.mles[0].borrow().len() / 2;
+
+        let mut s_i = Vec::with_capacity(d + 1);
+        for t in 0..=d {
+            let t = Scalar::from(t as u64);
+            let sum_t: Scalar = (0..half).map(|j| {
+                f.terms.iter().map(|(coeff, handles)| {
+                    *coeff * handles.iter().map(|&h| {
+                        let mle = f.mles[h].borrow();
+                        (Scalar::one() - t) * mle.evals[j] + t * mle.evals[j + half]
+                    }).product::<Scalar>()
+                }).sum::<Scalar>()
+            }).sum();
+            s_i.push(sum_t);
+        }
+
+        transcript.append_scalars(b"sumcheck-round-poly", &s_i);
+        let r_i = transcript.challenge_scalar(b"sumcheck-challenge");
+
+        f.fold_into_half(&r_i);
+
+        round_evals.push(s_i);
+    }
+
+    (claimed_sum, SumCheckProof { round_evals })
+}
+
+/// Verifies a sum-check proof for a `VirtualPolynomial` of the given
+/// `num_var` and `degree` (its `max_degree()`). Returns the final claim and
+/// the challenge point on success, in the variable order `f.evaluate` takes
+/// (the last challenge `r_n` first, since round `i` binds the highest variable
+/// still free), leaving the final check against `f.evaluate(point)` (or, for
+/// a zero-check, against the
+/// constraint-specific recomposition) to the caller.
+pub fn verify_virtual(num_var: usize, degree: usize, claimed_sum: Scalar, proof: &SumCheckProof, transcript: &mut Transcript) -> Option<(Scalar, Vec<Scalar>)> {
+    if proof.round_evals.len() != num_var {
+        return None;
+    }
+
+    transcript.append_scalar(b"sumcheck-claimed-sum", &claimed_sum);
+
+    let mut claim = claimed_sum;
+    let mut challenges = Vec::with_capacity(num_var);
+
+    for s_i in &proof.round_evals {
+        if s_i.len() != degree + 1 {
+            return None;
+        }
+        if s_i[0] + s_i[1] != claim {
+            return None;
+        }
+
+        transcript.append_scalars(b"sumcheck-round-poly", s_i);
+        let r_i = transcript.challenge_scalar(b"sumcheck-challenge");
+
+        claim = eval_univariate(s_i, &r_i);
+        challenges.push(r_i);
+    }
+
+    challenges.reverse();
+    Some((claim, challenges))
+}
+
+fn virtual_sum(f: &VirtualPolynomial) -> Scalar {
+    let len = f.mles[0].borrow().len();
+    (0..len).map(|i| {
+        f.terms.iter().map(|(coeff, handles)| {
+            *coeff * handles.iter().map(|&h| f.mles[h].borrow().evals[i]).product::<Scalar>()
+        }).sum::<Scalar>()
+    }).sum()
+}
+
+fn product_sum(factors: &[MLEPolynomial]) -> Scalar {
+    let len = factors[0].len();
+    (0..len).map(|i| factors.iter().map(|f| f.evals[i]).product::<Scalar>()).sum()
+}
+
+/// Evaluates the polynomial given by its values at `X = 0, 1, ..., evals.len()-1`
+/// at the point `x`, via Lagrange interpolation over that domain.
+fn eval_univariate(evals: &[Scalar], x: &Scalar) -> Scalar {
+    let basis = lagrange_basis_eval_all(evals.len(), x);
+    evals.iter().zip(basis.iter()).map(|(&e, &l)| e * l).sum()
+}
+
+/// Builds the factors of the standard "zero-check" instance: proves that
+/// `f` vanishes on every point of the hypercube by running sum-check on
+/// `g(X) = eq(beta, X) * f(X)`, which sums to zero over the hypercube iff
+/// `f` is identically zero there. `beta` is a random point chosen by the
+/// verifier (via the transcript) after `f` has been committed.
+pub fn zero_check_factors(f: MLEPolynomial, beta: &[Scalar]) -> Vec<MLEPolynomial> {
+    assert_eq!(beta.len(), f.num_var);
+    let eq_evals = EqPolynomial::new(&beta.to_vec()).evals_over_hypercube();
+    let eq_mle = MLEPolynomial { num_var: f.num_var, evals: eq_evals };
+    vec![eq_mle, f]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_factors() -> Vec<MLEPolynomial> {
+        vec![
+            MLEPolynomial::new(&Scalar::from_usize_vector(&[1,2,3,4])),
+            MLEPolynomial::new(&Scalar::from_usize_vector(&[5,6,7,8])),
+        ]
+    }
+
+    #[test]
+    fn test_prove_verify_roundtrip() {
+        let mut factors = sample_factors();
+        let mut prover_transcript = Transcript::new(b"test-sumcheck");
+        let (claimed_sum, proof) = prove(&mut factors, &mut prover_transcript);
+
+        let factors = sample_factors();
+        let mut verifier_transcript = Transcript::new(b"test-sumcheck");
+        assert!(verify(&factors, claimed_sum, &proof, &mut verifier_transcript));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_claimed_sum() {
+        let mut factors = sample_factors();
+        let mut prover_transcript = Transcript::new(b"test-sumcheck");
+        let (claimed_sum, proof) = prove(&mut factors, &mut prover_transcript);
+
+        let factors = sample_factors();
+        let mut verifier_transcript = Transcript::new(b"test-sumcheck");
+        assert!(!verify(&factors, claimed_sum + Scalar::one(), &proof, &mut verifier_transcript));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_round_poly() {
+        let mut factors = sample_factors();
+        let mut prover_transcript = Transcript::new(b"test-sumcheck");
+        let (claimed_sum, mut proof) = prove(&mut factors, &mut prover_transcript);
+        proof.round_evals[0][0] += Scalar::one();
+
+        let factors = sample_factors();
+        let mut verifier_transcript = Transcript::new(b"test-sumcheck");
+        assert!(!verify(&factors, claimed_sum, &proof, &mut verifier_transcript));
+    }
+
+    // Two R1CS constraints over z = [a, b, c, d]: a*b=c, d*d=d. Mirrors the
+    // fixture in `ccs::tests`.
+    fn virtual_zero_check_instance() -> crate::ccs::CCS {
+        use crate::ccs::{Matrix, R1CS};
+        let a = Matrix::new(2, 4, vec![(0, 0, Scalar::one()), (1, 3, Scalar::one())]);
+        let b = Matrix::new(2, 4, vec![(0, 1, Scalar::one()), (1, 3, Scalar::one())]);
+        let c = Matrix::new(2, 4, vec![(0, 2, Scalar::one()), (1, 3, Scalar::one())]);
+        R1CS { a, b, c }.to_ccs()
+    }
+
+    #[test]
+    fn test_prove_verify_virtual_roundtrip() {
+        let ccs = virtual_zero_check_instance();
+        let z = Scalar::from_usize_vector(&[2, 3, 6, 1]);
+        let beta = Scalar::from_usize_vector(&[7]);
+
+        let mut vp = ccs.zero_check_poly(&z, &beta);
+        let num_var = vp.num_var;
+        let degree = vp.max_degree();
+
+        let mut prover_transcript = Transcript::new(b"test-virtual-zero-check");
+        let (claimed_sum, proof) = prove_virtual(&mut vp, &mut prover_transcript);
+        assert_eq!(claimed_sum, Scalar::zero());
+
+        let mut verifier_transcript = Transcript::new(b"test-virtual-zero-check");
+        let (claim, challenges) = verify_virtual(num_var, degree, claimed_sum, &proof, &mut verifier_transcript)
+            .expect("honest proof must verify");
+
+        let fresh_vp = ccs.zero_check_poly(&z, &beta);
+        assert_eq!(claim, fresh_vp.evaluate(&challenges));
+    }
+
+    #[test]
+    fn test_verify_virtual_rejects_wrong_claimed_sum() {
+        let ccs = virtual_zero_check_instance();
+        let z = Scalar::from_usize_vector(&[2, 3, 6, 1]);
+        let beta = Scalar::from_usize_vector(&[7]);
+
+        let mut vp = ccs.zero_check_poly(&z, &beta);
+        let num_var = vp.num_var;
+        let degree = vp.max_degree();
+
+        let mut prover_transcript = Transcript::new(b"test-virtual-zero-check");
+        let (claimed_sum, proof) = prove_virtual(&mut vp, &mut prover_transcript);
+
+        let mut verifier_transcript = Transcript::new(b"test-virtual-zero-check");
+        let result = verify_virtual(num_var, degree, claimed_sum + Scalar::one(), &proof, &mut verifier_transcript);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_zero_check_roundtrip() {
+        let beta = Scalar::from_usize_vector(&[9, 10]);
+
+        // f is identically zero over the hypercube, so the zero-check sum is 0
+        let f = MLEPolynomial::new(&Scalar::from_usize_vector(&[0,0,0,0]));
+        let mut factors = zero_check_factors(f, &beta);
+        let mut prover_transcript = Transcript::new(b"test-zero-check");
+        let (claimed_sum, proof) = prove(&mut factors, &mut prover_transcript);
+        assert_eq!(claimed_sum, Scalar::zero());
+
+        let f = MLEPolynomial::new(&Scalar::from_usize_vector(&[0,0,0,0]));
+        let factors = zero_check_factors(f, &beta);
+        let mut verifier_transcript = Transcript::new(b"test-zero-check");
+        assert!(verify(&factors, claimed_sum, &proof, &mut verifier_transcript));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::*;
+
+/// A Fiat-Shamir transcript used to turn an interactive protocol
+/// (round messages, then a verifier challenge) into a non-interactive one.
+///
+/// Every message the prover sends is absorbed with [`Transcript::append_scalar`]
+/// before a challenge is squeezed out with [`Transcript::challenge_scalar`].
+/// Prover and verifier drive the same sequence of `append_*`/`challenge_*`
+/// calls, so they always agree on the challenges.
+///
+/// INSECURE: test-only. The state update is a low-degree algebraic map over
+/// `Scalar` (`state = state^2 + tag + s`), not a cryptographic hash. A prover
+/// can solve this map in the field to steer a round message `s_i` toward a
+/// chosen challenge `r_i`, which breaks Fiat-Shamir soundness. Do not use
+/// this for anything beyond exercising the surrounding protocol code; replace
+/// the state update with a real hash/sponge (e.g. Keccak or Poseidon) before
+/// relying on it for soundness.
+pub struct Transcript {
+    state: Scalar,
+}
+
+impl Transcript {
+    /// Starts a fresh transcript, domain-separated by `label`.
+    pub fn new(label: &'static [u8]) -> Self {
+        Transcript {
+            state: Scalar::from(Self::tag(label)),
+        }
+    }
+
+    /// Absorbs a scalar sent by the prover, domain-separated by `label`.
+    pub fn append_scalar(&mut self, label: &'static [u8], s: &Scalar) {
+        self.state = self.state * self.state + Scalar::from(Self::tag(label)) + *s;
+    }
+
+    /// Absorbs a vector of scalars, e.g. a round message of the sum-check
+    /// protocol.
+    pub fn append_scalars(&mut self, label: &'static [u8], v: &[Scalar]) {
+        for s in v {
+            self.append_scalar(label, s);
+        }
+    }
+
+    /// Squeezes a fresh challenge out of the transcript, domain-separated by
+    /// `label`. The challenge is itself absorbed back into the state, so two
+    /// calls never return the same value. See the [`Transcript`] doc for why
+    /// this is not safe to rely on for soundness.
+    pub fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar {
+        self.state = self.state * self.state + Scalar::from(Self::tag(label));
+        self.state
+    }
+
+    /// Squeezes `n` challenges, e.g. the random point `beta` used by a
+    /// zero-check.
+    pub fn challenge_vector(&mut self, label: &'static [u8], n: usize) -> Vec<Scalar> {
+        (0..n).map(|_| self.challenge_scalar(label)).collect()
+    }
+
+    fn tag(label: &'static [u8]) -> u64 {
+        label.iter().fold(0u64, |acc, &b| acc.wrapping_mul(31).wrapping_add(b as u64))
+    }
+}
@@ -0,0 +1,22 @@
+use crate::*;
+
+/// The Lagrange basis `L_0(x), ..., L_{n-1}(x)` over the domain
+/// `{0, 1, ..., n-1}`, evaluated at `x`: `L_i(x) = prod_{j != i} (x-j)/(i-j)`.
+///
+/// Shared kernel behind both [`crate::folding::lagrange_eval_all`] (combining
+/// witnesses at an instance index) and [`crate::sumcheck`]'s round-polynomial
+/// interpolation, so the two don't drift apart.
+pub fn lagrange_basis_eval_all(n: usize, x: &Scalar) -> Vec<Scalar> {
+    (0..n).map(|i| {
+        let mut num = Scalar::one();
+        let mut den = Scalar::one();
+        for j in 0..n {
+            if j == i {
+                continue;
+            }
+            num *= *x - Scalar::from(j as u64);
+            den *= Scalar::from(i as u64) - Scalar::from(j as u64);
+        }
+        num * den.inverse()
+    }).collect()
+}
@@ -0,0 +1,126 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use crate::*;
+use crate::mle::MLEPolynomial;
+
+/// A handle into a [`VirtualPolynomial`]'s pool of underlying MLEs, as
+/// returned by [`VirtualPolynomial::add_mle`].
+pub type MLEHandle = usize;
+
+/// A sum of products of shared [`MLEPolynomial`]s, over `num_var` shared
+/// variables:
+///
+/// ```text
+///   f(X) = sum_k c_k * prod_{j in S_k} mle_j(X)
+/// ```
+///
+/// This is the natural input to the sum-check prover: a single product (one
+/// term, `d` factors) is the special case `k = 1`, and a CCS/R1CS zero-check
+/// is a handful of such products sharing the same underlying matrix-vector
+/// MLEs.
+pub struct VirtualPolynomial {
+    pub num_var: usize,
+    pub mles: Vec<Rc<RefCell<MLEPolynomial>>>,
+    pub terms: Vec<(Scalar, Vec<MLEHandle>)>,
+}
+
+impl VirtualPolynomial {
+    pub fn new(num_var: usize) -> Self {
+        VirtualPolynomial {
+            num_var,
+            mles: Vec::new(),
+            terms: Vec::new(),
+        }
+    }
+
+    /// Registers an MLE in the shared pool and returns a handle to it, so it
+    /// can be reused across several product terms (e.g. the `eq` polynomial
+    /// of a zero-check, which multiplies every term).
+    pub fn add_mle(&mut self, mle: Rc<RefCell<MLEPolynomial>>) -> MLEHandle {
+        assert_eq!(mle.borrow().num_var, self.num_var);
+        self.mles.push(mle);
+        self.mles.len() - 1
+    }
+
+    /// Adds the term `coeff * prod_{j in handles} mle_j(X)`.
+    pub fn add_mle_product(&mut self, coeff: Scalar, handles: &[MLEHandle]) {
+        for &h in handles {
+            assert!(h < self.mles.len());
+        }
+        self.terms.push((coeff, handles.to_vec()));
+    }
+
+    /// The largest number of factors in any single term, which bounds the
+    /// degree of `f` in each single variable.
+    pub fn max_degree(&self) -> usize {
+        self.terms.iter().map(|(_, handles)| handles.len()).max().unwrap_or(0)
+    }
+
+    pub fn evaluate(&self, rs: &[Scalar]) -> Scalar {
+        assert_eq!(rs.len(), self.num_var);
+        self.terms.iter().map(|(coeff, handles)| {
+            *coeff * handles.iter().map(|&h| self.mles[h].borrow().evaluate(rs)).product::<Scalar>()
+        }).sum()
+    }
+
+    /// Sums `f(x)` over every point `x` of the hypercube. This is the claim
+    /// a sum-check proof on `f` starts from, before any variable is bound.
+    pub fn sum_over_hypercube(&self) -> Scalar {
+        let len = self.mles[0].borrow().len();
+        (0..len).map(|i| {
+            self.terms.iter().map(|(coeff, handles)| {
+                *coeff * handles.iter().map(|&h| self.mles[h].borrow().evals[i]).product::<Scalar>()
+            }).sum::<Scalar>()
+        }).sum()
+    }
+
+    /// Folds every underlying MLE once, e.g. to bind the next sum-check
+    /// variable. Each MLE is folded exactly once even if it is shared by
+    /// several terms, since `mles` holds it only once.
+    pub fn fold_into_half(&mut self, rho: &Scalar) {
+        for mle in &self.mles {
+            mle.borrow_mut().fold_into_half(rho);
+        }
+        self.num_var -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_matches_hand_computed_sum_of_products() {
+        let a = MLEPolynomial::new(&Scalar::from_usize_vector(&[1,2,3,4]));
+        let b = MLEPolynomial::new(&Scalar::from_usize_vector(&[5,6,7,8]));
+
+        let rs = Scalar::from_usize_vector(&[2,3]);
+        let a_at_rs = a.evaluate(&rs);
+        let b_at_rs = b.evaluate(&rs);
+
+        let mut vp = VirtualPolynomial::new(2);
+        let a_handle = vp.add_mle(Rc::new(RefCell::new(a)));
+        let b_handle = vp.add_mle(Rc::new(RefCell::new(b)));
+        // f(X) = 2 * a(X) * b(X) + 3 * a(X)
+        vp.add_mle_product(Scalar::from(2), &[a_handle, b_handle]);
+        vp.add_mle_product(Scalar::from(3), &[a_handle]);
+
+        let expected = Scalar::from(2) * a_at_rs * b_at_rs + Scalar::from(3) * a_at_rs;
+        assert_eq!(vp.evaluate(&rs), expected);
+        assert_eq!(vp.max_degree(), 2);
+    }
+
+    #[test]
+    fn test_fold_into_half_folds_shared_mle_once() {
+        let a = MLEPolynomial::new(&Scalar::from_usize_vector(&[1,2,3,4]));
+        let mut vp = VirtualPolynomial::new(2);
+        let a_handle = vp.add_mle(Rc::new(RefCell::new(a)));
+        vp.add_mle_product(Scalar::one(), &[a_handle, a_handle]);
+
+        vp.fold_into_half(&Scalar::from(5));
+
+        assert_eq!(vp.num_var, 1);
+        assert_eq!(vp.mles[a_handle].borrow().num_var, 1);
+    }
+}